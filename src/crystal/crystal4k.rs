@@ -0,0 +1,178 @@
+//! `Crystal4K`: a 4KB holographic boundary encoding of a settled [`QuorumField`].
+
+use crate::crystal::field::QuorumField;
+use crate::crystal::imatrix::ImportanceMatrix;
+
+/// Size in bytes of a compressed crystal.
+pub const CRYSTAL_BYTES: usize = 4096;
+
+/// Number of independent projections combined into the boundary encoding.
+const PROJECTIONS: usize = 3;
+
+/// A 4KB holographic projection of a settled [`QuorumField`] (≈38:1
+/// compression versus the field's 156KB of raw cell state).
+///
+/// Each of the [`PROJECTIONS`] projections samples the field's bits along a
+/// different stride, so that any contiguous region of the boundary still
+/// carries information drawn from across the whole field (the
+/// "holographic" property).
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Crystal4K {
+    pub(crate) boundary: Vec<u8>,
+}
+
+impl Crystal4K {
+    /// Projects a settled field down to its 4KB holographic boundary.
+    pub fn from_field(field: &QuorumField) -> Self {
+        let cells = field.cells();
+        let total_bits = CRYSTAL_BYTES * 8;
+        let bits_per_projection = total_bits / PROJECTIONS;
+        let mut boundary = vec![0u8; CRYSTAL_BYTES];
+
+        let mut out_bit = 0usize;
+        for projection in 0..PROJECTIONS {
+            for i in 0..bits_per_projection {
+                let bit = Self::sample_bit(cells, projection, i);
+                if bit {
+                    boundary[out_bit / 8] |= 1 << (out_bit % 8);
+                }
+                out_bit += 1;
+            }
+        }
+        Crystal4K { boundary }
+    }
+
+    /// Projects a settled field using an [`ImportanceMatrix`] instead of a
+    /// uniform stride, spending `target_bits` of output proportionally to
+    /// each cell's calibrated importance weight rather than splitting them
+    /// evenly — high-importance cells (the ones that tend to flip the
+    /// settled attractor) get more of the budget, low-importance ones get
+    /// less or none. Lets callers trade size for reconstruction fidelity at,
+    /// e.g., 2KB/4KB/8KB targets by varying `target_bits`.
+    pub fn from_field_with_imatrix(
+        field: &QuorumField,
+        imatrix: &ImportanceMatrix,
+        target_bits: usize,
+    ) -> Self {
+        let cells = field.cells();
+        let weights = imatrix.weights();
+        assert_eq!(
+            weights.len(),
+            cells.len(),
+            "imatrix was calibrated against a field with a different cell count"
+        );
+
+        let mut bit_budget: Vec<usize> = weights
+            .iter()
+            .map(|w| (w * target_bits as f32).round() as usize)
+            .collect();
+
+        // Rounding each cell's share independently can over/undershoot
+        // target_bits by a few bits; true that up on whichever cell got the
+        // largest budget so the boundary comes out to exactly `target_bits`.
+        let allocated: usize = bit_budget.iter().sum();
+        if let Some(max_idx) = bit_budget
+            .iter()
+            .enumerate()
+            .max_by_key(|(_, budget)| **budget)
+            .map(|(idx, _)| idx)
+        {
+            if allocated > target_bits {
+                bit_budget[max_idx] -= allocated - target_bits;
+            } else if allocated < target_bits {
+                bit_budget[max_idx] += target_bits - allocated;
+            }
+        }
+
+        let mut boundary = vec![0u8; target_bits.div_ceil(8)];
+        let mut out_bit = 0usize;
+        for (cell_idx, &budget) in bit_budget.iter().enumerate() {
+            let cell_bits = cells[cell_idx].len() * 64;
+            for i in 0..budget {
+                let bit_idx = (i * cell_bits) / budget;
+                let word = cells[cell_idx][bit_idx / 64];
+                if (word >> (bit_idx % 64)) & 1 == 1 {
+                    boundary[out_bit / 8] |= 1 << (out_bit % 8);
+                }
+                out_bit += 1;
+            }
+        }
+        Crystal4K { boundary }
+    }
+
+    /// Samples a single bit of `projection`'s view of the field, striding
+    /// across cells so each projection's bits are spread across the whole
+    /// field rather than clustered in one region.
+    fn sample_bit(cells: &[Vec<u64>], projection: usize, i: usize) -> bool {
+        let cell_words = cells[0].len();
+        let stride = projection + 1;
+        let global_bit = i.wrapping_mul(stride * 31 + 7);
+        let cell_idx = global_bit % cells.len();
+        let word_idx = (global_bit / 64) % cell_words;
+        let bit_idx = global_bit % 64;
+        (cells[cell_idx][word_idx] >> bit_idx) & 1 == 1
+    }
+
+    /// Hamming distance between two crystals' boundary bits.
+    ///
+    /// Panics if the two crystals have different boundary lengths — e.g. one
+    /// was compressed with [`from_field`](Self::from_field) (always
+    /// [`CRYSTAL_BYTES`]) and the other with
+    /// [`from_field_with_imatrix`](Self::from_field_with_imatrix) at a
+    /// different `target_bits`. Zipping them silently would truncate to the
+    /// shorter boundary and compare unrelated bit positions.
+    pub(crate) fn hamming_distance(&self, other: &Crystal4K) -> u32 {
+        assert_eq!(
+            self.boundary.len(),
+            other.boundary.len(),
+            "cannot compare crystals with different boundary lengths"
+        );
+        self.boundary
+            .iter()
+            .zip(other.boundary.iter())
+            .map(|(a, b)| (a ^ b).count_ones())
+            .sum()
+    }
+
+    /// Raw boundary bytes, for indexing layers built on top of `CrystalMemory`.
+    pub(crate) fn boundary(&self) -> &[u8] {
+        &self.boundary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crystal::field::QuorumField;
+    use crate::Fingerprint;
+
+    fn settled_field() -> QuorumField {
+        let mut field = QuorumField::new(4);
+        field.inject(&Fingerprint::from_content("crystal4k-test"));
+        field.settle(5);
+        field
+    }
+
+    #[test]
+    fn from_field_with_imatrix_rounds_bit_budget_to_target() {
+        let field = settled_field();
+        let imatrix = ImportanceMatrix::calibrate(std::slice::from_ref(&field));
+
+        // 2001 isn't evenly divisible by the field's 125 cells, which is
+        // exactly the case the bit-budget rounding correction has to handle.
+        let crystal = Crystal4K::from_field_with_imatrix(&field, &imatrix, 2001);
+        assert_eq!(crystal.boundary.len(), 2001usize.div_ceil(8));
+    }
+
+    #[test]
+    #[should_panic(expected = "different boundary lengths")]
+    fn hamming_distance_rejects_mixed_sizes() {
+        let small = Crystal4K {
+            boundary: vec![0u8; CRYSTAL_BYTES / 2],
+        };
+        let large = Crystal4K {
+            boundary: vec![0u8; CRYSTAL_BYTES],
+        };
+        small.hamming_distance(&large);
+    }
+}
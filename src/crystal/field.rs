@@ -0,0 +1,246 @@
+//! `QuorumField`: a 3D lattice of bit-cells that settle via neighbor quorum
+//! voting.
+
+use crate::crystal::lattice::Lattice;
+use crate::Fingerprint;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Lattice edge length (5×5×5 cells).
+pub const FIELD_SIZE: usize = 5;
+
+/// Bits stored per cell.
+const CELL_BITS: usize = 10_000;
+
+/// Words needed to pack one cell's bits.
+const CELL_WORDS: usize = CELL_BITS.div_ceil(64);
+
+/// Total number of bits in the field (5×5×5×10Kbit = 1,250,000).
+pub const FIELD_CELLS: usize = FIELD_SIZE * FIELD_SIZE * FIELD_SIZE * CELL_BITS;
+
+/// Number of cells in the cubic lattice (5×5×5 = 125).
+const NUM_CELLS: usize = FIELD_SIZE * FIELD_SIZE * FIELD_SIZE;
+
+/// Above this many bits, a cell's quorum vote switches to a rayon parallel
+/// iterator; below it the overhead of spinning up a thread pool dwarfs the
+/// per-bit vote. Cells (`NUM_CELLS` = 125) are too few to usefully
+/// parallelize over directly, so this is measured in bits per cell
+/// (`CELL_BITS` = 10,000) instead.
+#[cfg(feature = "rayon")]
+const PARALLEL_SETTLE_THRESHOLD: usize = 1_000;
+
+type Cell = Vec<u64>;
+
+fn empty_cell() -> Cell {
+    vec![0u64; CELL_WORDS]
+}
+
+fn get_bit(cell: &Cell, bit: usize) -> bool {
+    (cell[bit / 64] >> (bit % 64)) & 1 == 1
+}
+
+fn set_bit(cell: &mut Cell, bit: usize, value: bool) {
+    let mask = 1u64 << (bit % 64);
+    if value {
+        cell[bit / 64] |= mask;
+    } else {
+        cell[bit / 64] &= !mask;
+    }
+}
+
+fn cell_to_linear(x: usize, y: usize, z: usize) -> usize {
+    (z * FIELD_SIZE + y) * FIELD_SIZE + x
+}
+
+/// A 3D lattice of quorum-voting bit cells.
+///
+/// Each cell holds `CELL_BITS` bits. On every [`settle`](QuorumField::settle)
+/// step, each cell looks at its neighbors in the chosen [`Lattice`]'s
+/// coordination shell (toroidally wrapped) and sets each bit to 1 if at least
+/// `threshold` of those neighbors have it set, and to 0 otherwise.
+pub struct QuorumField {
+    cells: Vec<Cell>,
+    threshold: usize,
+    lattice: Lattice,
+    neighbor_table: Vec<Vec<usize>>,
+}
+
+impl QuorumField {
+    /// Creates a 5×5×5 simple-cubic field with the given quorum threshold
+    /// (out of 6 neighbors).
+    pub fn new(threshold: usize) -> Self {
+        Self::with_lattice(Lattice::Cubic, threshold)
+    }
+
+    /// Creates a 5×5×5 field over the given lattice topology, with an
+    /// explicit quorum threshold (out of `lattice.coordination_number()`
+    /// neighbors). Callers who just want the lattice's natural majority can
+    /// pass `lattice.majority_threshold()`.
+    pub fn with_lattice(lattice: Lattice, threshold: usize) -> Self {
+        let neighbor_table = (0..NUM_CELLS)
+            .map(|idx| Self::neighbors_of(idx, lattice))
+            .collect();
+        QuorumField {
+            cells: (0..NUM_CELLS).map(|_| empty_cell()).collect(),
+            threshold,
+            lattice,
+            neighbor_table,
+        }
+    }
+
+    /// The lattice's coordination-shell neighbor indices of `idx`, with
+    /// toroidal wraparound on the 5×5×5 index grid.
+    fn neighbors_of(idx: usize, lattice: Lattice) -> Vec<usize> {
+        let x = idx % FIELD_SIZE;
+        let y = (idx / FIELD_SIZE) % FIELD_SIZE;
+        let z = idx / (FIELD_SIZE * FIELD_SIZE);
+        let wrap = |v: isize| v.rem_euclid(FIELD_SIZE as isize) as usize;
+        lattice
+            .neighbor_offsets()
+            .into_iter()
+            .map(|(dx, dy, dz)| {
+                cell_to_linear(
+                    wrap(x as isize + dx),
+                    wrap(y as isize + dy),
+                    wrap(z as isize + dz),
+                )
+            })
+            .collect()
+    }
+
+    /// Seeds the field with a fingerprint, XOR-mixing fingerprint bit
+    /// `cell_idx * CELL_BITS + bit` into bit `bit` of cell `cell_idx`.
+    pub fn inject(&mut self, pattern: &Fingerprint) {
+        for (cell_idx, cell) in self.cells.iter_mut().enumerate() {
+            for bit in 0..CELL_BITS {
+                let global_bit = cell_idx * CELL_BITS + bit;
+                if (pattern.words[global_bit / 64] >> (global_bit % 64)) & 1 == 1 {
+                    cell[bit / 64] ^= 1u64 << (bit % 64);
+                }
+            }
+        }
+    }
+
+    /// Computes cell `idx`'s next state from `snapshot`, the immutable
+    /// pre-step buffer, by majority vote over its neighbors.
+    ///
+    /// With the `rayon` feature, the per-bit vote (there are `CELL_BITS` of
+    /// them) runs as a parallel iterator once `CELL_BITS` crosses
+    /// [`PARALLEL_SETTLE_THRESHOLD`] — parallelizing over the 125 cells
+    /// themselves wouldn't pay off, but parallelizing over each cell's
+    /// 10,000-bit vote does.
+    fn next_cell_state(snapshot: &[Cell], neighbors: &[usize], threshold: usize) -> Cell {
+        let vote = |bit: usize| -> bool {
+            let votes = neighbors
+                .iter()
+                .filter(|&&n| get_bit(&snapshot[n], bit))
+                .count();
+            votes >= threshold
+        };
+
+        #[cfg(feature = "rayon")]
+        {
+            if CELL_BITS >= PARALLEL_SETTLE_THRESHOLD {
+                let bits: Vec<bool> = (0..CELL_BITS).into_par_iter().map(vote).collect();
+                let mut next = empty_cell();
+                for (bit, value) in bits.into_iter().enumerate() {
+                    set_bit(&mut next, bit, value);
+                }
+                return next;
+            }
+        }
+
+        let mut next = empty_cell();
+        for bit in 0..CELL_BITS {
+            set_bit(&mut next, bit, vote(bit));
+        }
+        next
+    }
+
+    /// Runs up to `steps` quorum-voting steps using double-buffering: each
+    /// cell's next state is computed from an immutable snapshot of the
+    /// current step, written into a second buffer, and the buffers are then
+    /// swapped. This removes read/write races between cells within a step,
+    /// which is what lets [`next_cell_state`](Self::next_cell_state)'s
+    /// per-bit vote run as a parallel iterator.
+    ///
+    /// Returns `(steps_taken, converged)`, where `converged` is `true` if
+    /// the field reached a fixed point before exhausting `steps`.
+    pub fn settle(&mut self, steps: usize) -> (usize, bool) {
+        let threshold = self.threshold;
+        let neighbor_table = &self.neighbor_table;
+        for step in 0..steps {
+            let snapshot = &self.cells;
+            let next: Vec<Cell> = (0..snapshot.len())
+                .map(|idx| Self::next_cell_state(snapshot, &neighbor_table[idx], threshold))
+                .collect();
+
+            let converged = next == self.cells;
+            self.cells = next;
+            if converged {
+                return (step + 1, true);
+            }
+        }
+        (steps, false)
+    }
+
+    /// Raw bits of every cell in the field, used by [`Crystal4K::from_field`].
+    ///
+    /// [`Crystal4K::from_field`]: crate::crystal::Crystal4K::from_field
+    pub(crate) fn cells(&self) -> &[Vec<u64>] {
+        &self.cells
+    }
+
+    /// The lattice topology this field was created with.
+    pub fn lattice(&self) -> Lattice {
+        self.lattice
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Plain sequential re-implementation of the per-bit quorum vote, kept
+    /// independent of the `rayon` feature so it can be checked against
+    /// [`QuorumField::next_cell_state`] regardless of which path that takes.
+    fn serial_vote(snapshot: &[Cell], neighbors: &[usize], threshold: usize) -> Cell {
+        let mut next = empty_cell();
+        for bit in 0..CELL_BITS {
+            let votes = neighbors
+                .iter()
+                .filter(|&&n| get_bit(&snapshot[n], bit))
+                .count();
+            set_bit(&mut next, bit, votes >= threshold);
+        }
+        next
+    }
+
+    #[test]
+    fn next_cell_state_matches_serial_vote() {
+        let mut field = QuorumField::new(4);
+        field.inject(&Fingerprint::from_content("field-parallel-test"));
+        let snapshot = field.cells().to_vec();
+        let neighbors = &field.neighbor_table[0];
+
+        let parallel = QuorumField::next_cell_state(&snapshot, neighbors, field.threshold);
+        let serial = serial_vote(&snapshot, neighbors, field.threshold);
+        assert_eq!(parallel, serial);
+    }
+
+    #[test]
+    fn settle_is_deterministic() {
+        let mut a = QuorumField::new(4);
+        let mut b = QuorumField::new(4);
+        let seed = Fingerprint::from_content("field-determinism-test");
+        a.inject(&seed);
+        b.inject(&seed);
+
+        let result_a = a.settle(10);
+        let result_b = b.settle(10);
+
+        assert_eq!(result_a, result_b);
+        assert_eq!(a.cells(), b.cells());
+    }
+}
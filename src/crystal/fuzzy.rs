@@ -0,0 +1,184 @@
+//! N-gram inverted index for fuzzy crystal retrieval.
+//!
+//! `CrystalMemory::infer` only ever returns the single settled attractor.
+//! Sometimes a caller wants crystals *near* a query instead — the way
+//! n-gram text matching finds documents sharing shingles without an exact
+//! match. We slide a `k`-bit window across each crystal's boundary bits,
+//! hash each window into a token, and build an inverted index from token to
+//! crystal id. A query is tokenized the same way and candidates are ranked
+//! by Jaccard overlap over their token sets.
+
+use crate::crystal::crystal4k::Crystal4K;
+use std::collections::{HashMap, HashSet};
+
+/// Default n-gram window width, in bits.
+pub const DEFAULT_WINDOW_BITS: usize = 16;
+
+fn get_bit(bytes: &[u8], bit: usize) -> bool {
+    (bytes[bit / 8] >> (bit % 8)) & 1 == 1
+}
+
+/// FNV-1a offset basis / prime, used to fold a `k`-bit window of arbitrary
+/// width down to a single `u64` token without the systematic collisions a
+/// `1 << (offset % 64)` bit-packing would produce once `k` exceeds 64 (bits
+/// `offset` and `offset + 64` would otherwise land on the same position).
+const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Hashes a single `k`-bit window starting at bit `start` into a `u64` token.
+fn hash_window(boundary: &[u8], start: usize, k: usize) -> u64 {
+    let mut token = FNV_OFFSET_BASIS;
+    for offset in 0..k {
+        token ^= get_bit(boundary, start + offset) as u64;
+        token = token.wrapping_mul(FNV_PRIME);
+    }
+    token
+}
+
+/// Tokenizes a crystal's boundary into overlapping `k`-bit windows, hashed
+/// into `u64` tokens.
+fn tokenize(crystal: &Crystal4K, k: usize) -> HashSet<u64> {
+    let boundary = crystal.boundary();
+    let total_bits = boundary.len() * 8;
+    let mut tokens = HashSet::new();
+    if total_bits < k {
+        return tokens;
+    }
+    for start in 0..=(total_bits - k) {
+        tokens.insert(hash_window(boundary, start, k));
+    }
+    tokens
+}
+
+fn jaccard(a: &HashSet<u64>, b: &HashSet<u64>) -> f32 {
+    if a.is_empty() && b.is_empty() {
+        return 0.0;
+    }
+    let intersection = a.intersection(b).count();
+    let union = a.union(b).count();
+    intersection as f32 / union as f32
+}
+
+/// An inverted index from n-gram token to the crystal ids containing it,
+/// supporting approximate nearest-neighbor style fuzzy retrieval.
+pub(crate) struct NgramIndex {
+    k: usize,
+    postings: HashMap<u64, Vec<usize>>,
+    token_sets: Vec<HashSet<u64>>,
+}
+
+impl NgramIndex {
+    /// Builds an index over `crystals` using a `k`-bit sliding window.
+    pub(crate) fn build(crystals: &[Crystal4K], k: usize) -> Self {
+        let mut index = NgramIndex {
+            k,
+            postings: HashMap::new(),
+            token_sets: Vec::with_capacity(crystals.len()),
+        };
+        for crystal in crystals {
+            index.insert(crystal);
+        }
+        index
+    }
+
+    /// Tokenizes `crystal` and adds it to the index under the next crystal id.
+    pub(crate) fn insert(&mut self, crystal: &Crystal4K) {
+        let id = self.token_sets.len();
+        let tokens = tokenize(crystal, self.k);
+        for &token in &tokens {
+            self.postings.entry(token).or_default().push(id);
+        }
+        self.token_sets.push(tokens);
+    }
+
+    /// Ranks stored crystal ids by token-set Jaccard overlap with `query`,
+    /// returning the top `top_m` as `(crystal_id, score)`.
+    ///
+    /// Only crystals sharing at least one token with the query are
+    /// considered candidates, which is what keeps this sublinear versus a
+    /// full settle-based scan.
+    pub(crate) fn search(&self, query: &Crystal4K, top_m: usize) -> Vec<(usize, f32)> {
+        let query_tokens = tokenize(query, self.k);
+
+        let mut candidates: HashSet<usize> = HashSet::new();
+        for token in &query_tokens {
+            if let Some(ids) = self.postings.get(token) {
+                candidates.extend(ids);
+            }
+        }
+
+        let mut scored: Vec<(usize, f32)> = candidates
+            .into_iter()
+            .map(|id| (id, jaccard(&query_tokens, &self.token_sets[id])))
+            .collect();
+        scored.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap());
+        scored.truncate(top_m);
+        scored
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crystal(bytes: &[u8]) -> Crystal4K {
+        Crystal4K {
+            boundary: bytes.to_vec(),
+        }
+    }
+
+    #[test]
+    fn windows_wider_than_64_bits_dont_collide() {
+        // Bits 64-71 (byte index 8) differ between these two windows; a
+        // `1 << (offset % 64)` packing would fold bit 64 onto bit 0's
+        // position and miss the difference entirely.
+        let a = vec![0u8; 16];
+        let mut b = a.clone();
+        b[8] = 0xFF;
+
+        assert_ne!(hash_window(&a, 0, 128), hash_window(&b, 0, 128));
+    }
+
+    #[test]
+    fn exact_match_scores_top_with_jaccard_one() {
+        let target = crystal(&[0xA5; 64]);
+        let unrelated = crystal(&[0x00; 64]);
+        let index = NgramIndex::build(
+            &[target.clone(), unrelated],
+            DEFAULT_WINDOW_BITS,
+        );
+
+        let results = index.search(&target, 2);
+        assert_eq!(results[0].0, 0);
+        assert_eq!(results[0].1, 1.0);
+    }
+
+    /// Non-repeating fill so n-gram windows don't collapse into a handful of
+    /// unique tokens the way a constant byte value would.
+    fn pseudo_random_bytes(seed: u8) -> Vec<u8> {
+        let mut state = seed;
+        (0..64)
+            .map(|_| {
+                state = state.wrapping_mul(131).wrapping_add(7);
+                state
+            })
+            .collect()
+    }
+
+    #[test]
+    fn near_match_outranks_unrelated_crystal() {
+        let target_bytes = pseudo_random_bytes(1);
+        let mut near_bytes = target_bytes.clone();
+        near_bytes[0] ^= 0x01; // a single bit off from `target`
+
+        let target = crystal(&target_bytes);
+        let near = crystal(&near_bytes);
+        let unrelated = crystal(&pseudo_random_bytes(200));
+
+        let index = NgramIndex::build(&[near.clone(), unrelated.clone()], DEFAULT_WINDOW_BITS);
+
+        let results = index.search(&target, 2);
+        assert_eq!(results[0].0, 0, "near-identical crystal should rank first");
+        assert!(results[0].1 > 0.9);
+    }
+}
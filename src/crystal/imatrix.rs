@@ -0,0 +1,68 @@
+//! Importance-matrix calibration for importance-weighted crystal compression.
+//!
+//! Borrowed from the imatrix idea in low-bit model quantization: run a
+//! calibration set of settled fields, track how much each cell's activation
+//! varies across them, and let [`Crystal4K::from_field_with_imatrix`] spend
+//! more of its bit budget on high-variance (high-importance) cells instead
+//! of sampling every region uniformly.
+//!
+//! [`Crystal4K::from_field_with_imatrix`]: crate::crystal::Crystal4K::from_field_with_imatrix
+
+use crate::crystal::field::QuorumField;
+
+/// Per-cell importance scores gathered from a calibration set of settled
+/// fields, used to weight where [`Crystal4K::from_field_with_imatrix`]
+/// spends its projection bits.
+///
+/// [`Crystal4K::from_field_with_imatrix`]: crate::crystal::Crystal4K::from_field_with_imatrix
+pub struct ImportanceMatrix {
+    /// One score per cell, higher meaning "more often decisive".
+    pub(crate) scores: Vec<f32>,
+}
+
+impl ImportanceMatrix {
+    /// Calibrates an importance score per cell from a set of settled
+    /// fields, using each cell's activation variance across the set as a
+    /// proxy for how often it's decisive for the settled attractor: a cell
+    /// that's always all-zeros or all-ones carries no information, while one
+    /// that varies a lot across calibration fields is doing real work.
+    pub fn calibrate(fields: &[QuorumField]) -> Self {
+        assert!(!fields.is_empty(), "calibration requires at least one field");
+
+        let num_cells = fields[0].cells().len();
+        let mut scores = Vec::with_capacity(num_cells);
+
+        for cell_idx in 0..num_cells {
+            let activations: Vec<f64> = fields
+                .iter()
+                .map(|field| Self::activation_fraction(&field.cells()[cell_idx]))
+                .collect();
+            scores.push(Self::variance(&activations) as f32);
+        }
+
+        ImportanceMatrix { scores }
+    }
+
+    /// Fraction of set bits in a cell, a cheap proxy for its activation level.
+    fn activation_fraction(cell: &[u64]) -> f64 {
+        let set_bits: u32 = cell.iter().map(|w| w.count_ones()).sum();
+        let total_bits = (cell.len() * 64) as f64;
+        set_bits as f64 / total_bits
+    }
+
+    fn variance(samples: &[f64]) -> f64 {
+        let mean = samples.iter().sum::<f64>() / samples.len() as f64;
+        samples.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / samples.len() as f64
+    }
+
+    /// Normalized per-cell bit-budget weights (summing to 1.0), used to
+    /// split a target bit count proportionally across cells.
+    pub(crate) fn weights(&self) -> Vec<f32> {
+        let total: f32 = self.scores.iter().sum();
+        if total <= 0.0 {
+            let uniform = 1.0 / self.scores.len() as f32;
+            return vec![uniform; self.scores.len()];
+        }
+        self.scores.iter().map(|s| s / total).collect()
+    }
+}
@@ -0,0 +1,181 @@
+//! Vantage-point tree over crystal boundary Hamming distance.
+//!
+//! `CrystalMemory::infer` otherwise has to scan every stored crystal to find
+//! the nearest attractor. A VP-tree prunes that search: pick a pivot,
+//! compute the median Hamming radius to everything else, and recurse into
+//! "inside" (closer than the radius) and "outside" (farther) subtrees. At
+//! query time we descend the subtree the query falls into first, then
+//! backtrack into the other subtree only when the triangle-inequality bound
+//! `|d(query, pivot) - radius|` is still smaller than the best distance
+//! found so far.
+
+use crate::crystal::crystal4k::Crystal4K;
+
+struct Node {
+    pivot: Crystal4K,
+    radius: u32,
+    inside: Option<Box<Node>>,
+    outside: Option<Box<Node>>,
+}
+
+/// A vantage-point tree over stored crystals, keyed on boundary Hamming distance.
+pub(crate) struct VpTree {
+    root: Option<Box<Node>>,
+}
+
+impl VpTree {
+    /// Builds a balanced tree from `items`.
+    pub(crate) fn build(mut items: Vec<Crystal4K>) -> Self {
+        VpTree {
+            root: Self::build_node(&mut items),
+        }
+    }
+
+    fn build_node(items: &mut Vec<Crystal4K>) -> Option<Box<Node>> {
+        if items.is_empty() {
+            return None;
+        }
+        let pivot = items.swap_remove(0);
+        if items.is_empty() {
+            return Some(Box::new(Node {
+                pivot,
+                radius: 0,
+                inside: None,
+                outside: None,
+            }));
+        }
+
+        let mut distances: Vec<u32> = items.iter().map(|c| c.hamming_distance(&pivot)).collect();
+        let mid = distances.len() / 2;
+        let (_, &mut radius, _) = distances.select_nth_unstable(mid);
+
+        let mut inside_items = Vec::new();
+        let mut outside_items = Vec::new();
+        for (item, distance) in items.drain(..).zip(distances) {
+            if distance <= radius {
+                inside_items.push(item);
+            } else {
+                outside_items.push(item);
+            }
+        }
+
+        Some(Box::new(Node {
+            pivot,
+            radius,
+            inside: Self::build_node(&mut inside_items),
+            outside: Self::build_node(&mut outside_items),
+        }))
+    }
+
+    /// Inserts a single crystal, descending to the subtree it falls into
+    /// without rebalancing. Good enough to keep the tree usable between
+    /// full rebuilds; see [`CrystalMemory::build_index`].
+    ///
+    /// [`CrystalMemory::build_index`]: crate::crystal::CrystalMemory::build_index
+    pub(crate) fn insert(&mut self, item: Crystal4K) {
+        match &mut self.root {
+            None => {
+                self.root = Some(Box::new(Node {
+                    pivot: item,
+                    radius: 0,
+                    inside: None,
+                    outside: None,
+                }))
+            }
+            Some(node) => Self::insert_node(node, item),
+        }
+    }
+
+    fn insert_node(node: &mut Node, item: Crystal4K) {
+        let distance = item.hamming_distance(&node.pivot);
+        let branch = if distance <= node.radius {
+            &mut node.inside
+        } else {
+            &mut node.outside
+        };
+        match branch {
+            Some(child) => Self::insert_node(child, item),
+            None => {
+                *branch = Some(Box::new(Node {
+                    pivot: item,
+                    radius: 0,
+                    inside: None,
+                    outside: None,
+                }))
+            }
+        }
+    }
+
+    /// Finds the stored crystal nearest `query`, returning `(distance, crystal)`.
+    pub(crate) fn nearest(&self, query: &Crystal4K) -> Option<(u32, Crystal4K)> {
+        let mut best: Option<(u32, Crystal4K)> = None;
+        if let Some(root) = &self.root {
+            Self::search(root, query, &mut best);
+        }
+        best
+    }
+
+    fn search(node: &Node, query: &Crystal4K, best: &mut Option<(u32, Crystal4K)>) {
+        let distance = query.hamming_distance(&node.pivot);
+        if best.as_ref().is_none_or(|(d, _)| distance < *d) {
+            *best = Some((distance, node.pivot.clone()));
+        }
+
+        let (near, far) = if distance <= node.radius {
+            (&node.inside, &node.outside)
+        } else {
+            (&node.outside, &node.inside)
+        };
+
+        if let Some(near) = near {
+            Self::search(near, query, best);
+        }
+
+        let bound = (distance as i64 - node.radius as i64).unsigned_abs() as u32;
+        let still_worth_it = best.as_ref().is_none_or(|(d, _)| bound < *d);
+        if still_worth_it {
+            if let Some(far) = far {
+                Self::search(far, query, best);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random crystal, so the test is reproducible
+    /// without pulling in a `rand` dependency.
+    fn make_crystal(seed: u64) -> Crystal4K {
+        let mut state = seed.wrapping_add(0x9E3779B97F4A7C15);
+        let boundary = (0..64)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                (state >> 56) as u8
+            })
+            .collect();
+        Crystal4K { boundary }
+    }
+
+    fn linear_nearest(crystals: &[Crystal4K], query: &Crystal4K) -> u32 {
+        crystals
+            .iter()
+            .map(|c| c.hamming_distance(query))
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn nearest_matches_linear_scan() {
+        let crystals: Vec<Crystal4K> = (0..300).map(|i| make_crystal(i + 1)).collect();
+        let tree = VpTree::build(crystals.clone());
+
+        for q in 0..20 {
+            let query = make_crystal(10_000 + q);
+            let expected = linear_nearest(&crystals, &query);
+            let (actual, _) = tree.nearest(&query).expect("tree is non-empty");
+            assert_eq!(actual, expected);
+        }
+    }
+}
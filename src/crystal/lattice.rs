@@ -0,0 +1,135 @@
+//! Crystal lattice topologies: coordination numbers and neighbor offsets.
+//!
+//! `QuorumField` cells live on a 5×5×5 index grid regardless of lattice; what
+//! changes per lattice is which other cells on that grid count as a given
+//! cell's neighbors (its coordination shell) and how many of them must agree
+//! before a bit flips.
+
+/// A crystal lattice topology, each with its own coordination number (count
+/// of nearest neighbors).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Lattice {
+    /// Simple cubic: 6 face-adjacent neighbors.
+    Cubic,
+    /// Body-centered cubic: 8 neighbors along the body diagonals.
+    Bcc,
+    /// Face-centered cubic: 12 neighbors along the face diagonals.
+    Fcc,
+    /// Hexagonal close-packed: 12 neighbors (6 in-plane, 3 above, 3 below).
+    Hcp,
+}
+
+impl Lattice {
+    /// Number of nearest neighbors a cell has in this lattice.
+    pub fn coordination_number(self) -> usize {
+        self.neighbor_offsets().len()
+    }
+
+    /// A quorum threshold of "strict majority of the coordination number",
+    /// the sensible default for each lattice (4/6 for cubic, 5/8 for bcc,
+    /// 7/12 for fcc and hcp).
+    pub fn majority_threshold(self) -> usize {
+        self.coordination_number() / 2 + 1
+    }
+
+    /// Neighbor offsets `(dx, dy, dz)` on the cubic index grid, one per
+    /// neighbor in this lattice's coordination shell.
+    ///
+    /// These are integer approximations of each lattice's true geometry,
+    /// projected onto `QuorumField`'s cubic index grid rather than the
+    /// lattice's native (non-cubic) basis vectors.
+    pub fn neighbor_offsets(self) -> Vec<(isize, isize, isize)> {
+        match self {
+            Lattice::Cubic => vec![
+                (-1, 0, 0),
+                (1, 0, 0),
+                (0, -1, 0),
+                (0, 1, 0),
+                (0, 0, -1),
+                (0, 0, 1),
+            ],
+            Lattice::Bcc => {
+                let mut offsets = Vec::with_capacity(8);
+                for &dx in &[-1, 1] {
+                    for &dy in &[-1, 1] {
+                        for &dz in &[-1, 1] {
+                            offsets.push((dx, dy, dz));
+                        }
+                    }
+                }
+                offsets
+            }
+            Lattice::Fcc => vec![
+                (-1, -1, 0),
+                (-1, 1, 0),
+                (1, -1, 0),
+                (1, 1, 0),
+                (-1, 0, -1),
+                (-1, 0, 1),
+                (1, 0, -1),
+                (1, 0, 1),
+                (0, -1, -1),
+                (0, -1, 1),
+                (0, 1, -1),
+                (0, 1, 1),
+            ],
+            Lattice::Hcp => vec![
+                // 6 in-plane hexagonal-ring neighbors.
+                (-1, 0, 0),
+                (1, 0, 0),
+                (0, -1, 0),
+                (0, 1, 0),
+                (-1, 1, 0),
+                (1, -1, 0),
+                // 3 above, 3 below.
+                (0, 0, -1),
+                (1, 0, -1),
+                (0, 1, -1),
+                (0, 0, 1),
+                (-1, 0, 1),
+                (0, -1, 1),
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    const ALL: [Lattice; 4] = [Lattice::Cubic, Lattice::Bcc, Lattice::Fcc, Lattice::Hcp];
+
+    #[test]
+    fn coordination_number_matches_offset_count() {
+        for lattice in ALL {
+            assert_eq!(lattice.coordination_number(), lattice.neighbor_offsets().len());
+        }
+    }
+
+    #[test]
+    fn neighbor_offsets_have_no_self_or_duplicates() {
+        for lattice in ALL {
+            let offsets = lattice.neighbor_offsets();
+            assert!(
+                offsets.iter().all(|&(dx, dy, dz)| (dx, dy, dz) != (0, 0, 0)),
+                "{lattice:?} neighbor offsets include a self-reference"
+            );
+
+            let unique: HashSet<_> = offsets.iter().copied().collect();
+            assert_eq!(
+                unique.len(),
+                offsets.len(),
+                "{lattice:?} neighbor offsets contain a duplicate"
+            );
+        }
+    }
+
+    #[test]
+    fn majority_threshold_is_strict_majority() {
+        assert_eq!(Lattice::Cubic.majority_threshold(), 4);
+        assert_eq!(Lattice::Bcc.majority_threshold(), 5);
+        assert_eq!(Lattice::Fcc.majority_threshold(), 7);
+        assert_eq!(Lattice::Hcp.majority_threshold(), 7);
+    }
+}
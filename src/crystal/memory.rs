@@ -0,0 +1,244 @@
+//! `CrystalMemory`: a store of [`Crystal4K`] crystals with settle-into-attractor
+//! inference.
+
+use crate::crystal::crystal4k::Crystal4K;
+use crate::crystal::fuzzy::NgramIndex;
+use crate::crystal::index::VpTree;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// Default number of crystals a fresh [`CrystalMemory`] is sized for (43K × 4KB ≈ 170MB).
+pub const DEFAULT_CAPACITY: usize = 43_000;
+
+/// Default number of [`settle`](crate::crystal::QuorumField::settle) steps used
+/// when callers settle a query field before looking it up.
+pub const DEFAULT_SETTLE_STEPS: usize = 100;
+
+/// Above this many stored crystals, `infer` partitions the scan into
+/// rayon-parallel chunks instead of scanning linearly.
+#[cfg(feature = "rayon")]
+const PARALLEL_INFER_THRESHOLD: usize = 1_000;
+
+/// Crystals per chunk when `infer` scans in parallel.
+#[cfg(feature = "rayon")]
+const INFER_CHUNK_SIZE: usize = 1024;
+
+/// A store of settled crystals, queried by nearest-attractor inference.
+///
+/// All crystals in a given `CrystalMemory` must share one boundary width:
+/// [`Crystal4K::from_field`] always produces a fixed-size boundary, but
+/// [`Crystal4K::from_field_with_imatrix`] lets callers pick any
+/// `target_bits`, so mixing e.g. a 4KB crystal with a 2KB one in the same
+/// store would otherwise only fail deep inside a distance comparison.
+/// [`add`](Self::add) enforces this up front instead — compress to one
+/// target size per store, and use a separate `CrystalMemory` per target if
+/// you need more than one.
+pub struct CrystalMemory {
+    crystals: Vec<Crystal4K>,
+    index: Option<VpTree>,
+    fuzzy_index: Option<NgramIndex>,
+}
+
+/// Summary statistics over a [`CrystalMemory`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MemoryStats {
+    pub count: usize,
+}
+
+impl CrystalMemory {
+    /// Creates an empty memory sized for [`DEFAULT_CAPACITY`] crystals.
+    pub fn new() -> Self {
+        CrystalMemory {
+            crystals: Vec::with_capacity(DEFAULT_CAPACITY),
+            index: None,
+            fuzzy_index: None,
+        }
+    }
+
+    /// Builds a [`VpTree`] index over the crystals currently stored, so that
+    /// subsequent `infer` calls (and `add`s that follow) prune the search
+    /// instead of scanning linearly.
+    pub fn build_index(&mut self) {
+        self.index = Some(VpTree::build(self.crystals.clone()));
+    }
+
+    /// Builds an n-gram inverted index over the crystals currently stored,
+    /// using a `window_bits`-wide sliding window, so that subsequent
+    /// [`search_fuzzy`](Self::search_fuzzy) calls (and `add`s that follow)
+    /// don't need a full settle comparison against every crystal.
+    pub fn build_fuzzy_index(&mut self, window_bits: usize) {
+        self.fuzzy_index = Some(NgramIndex::build(&self.crystals, window_bits));
+    }
+
+    /// Stores a crystal, incrementally inserting it into whichever indexes
+    /// have been built.
+    ///
+    /// Panics if `crystal`'s boundary is a different length than crystals
+    /// already in this store — see the [`CrystalMemory`] type docs.
+    pub fn add(&mut self, crystal: Crystal4K) {
+        if let Some(existing) = self.crystals.first() {
+            assert_eq!(
+                crystal.boundary().len(),
+                existing.boundary().len(),
+                "CrystalMemory holds one boundary width per store: this store holds {}-byte \
+                 crystals, got a {}-byte one — use a separate CrystalMemory per compression target",
+                existing.boundary().len(),
+                crystal.boundary().len(),
+            );
+        }
+        if let Some(index) = &mut self.index {
+            index.insert(crystal.clone());
+        }
+        if let Some(fuzzy_index) = &mut self.fuzzy_index {
+            fuzzy_index.insert(&crystal);
+        }
+        self.crystals.push(crystal);
+    }
+
+    /// Finds up to `top_m` crystals *near* `query` by n-gram token overlap
+    /// over their boundary bits, ranked by Jaccard similarity — useful when
+    /// a caller wants approximate neighbors instead of the single settled
+    /// attractor [`infer`](Self::infer) returns.
+    ///
+    /// Requires [`build_fuzzy_index`](Self::build_fuzzy_index) (with a
+    /// [`DEFAULT_WINDOW_BITS`](crate::crystal::DEFAULT_WINDOW_BITS)-or-caller-chosen
+    /// window) to have been called first; returns an empty vec otherwise.
+    pub fn search_fuzzy(&self, query: &Crystal4K, top_m: usize) -> Vec<Crystal4K> {
+        let Some(fuzzy_index) = &self.fuzzy_index else {
+            return Vec::new();
+        };
+        fuzzy_index
+            .search(query, top_m)
+            .into_iter()
+            .map(|(id, _score)| self.crystals[id].clone())
+            .collect()
+    }
+
+    /// Summary statistics over the stored crystals.
+    pub fn stats(&self) -> MemoryStats {
+        MemoryStats {
+            count: self.crystals.len(),
+        }
+    }
+
+    /// Finds the stored crystal closest to `query` by boundary Hamming
+    /// distance — the attractor `query` would settle toward.
+    ///
+    /// Uses the [`VpTree`] index when [`build_index`](Self::build_index) has
+    /// been called, pruning to a small candidate set via the triangle
+    /// inequality instead of comparing against every stored crystal.
+    /// Otherwise falls back to a linear scan; with the `rayon` feature, that
+    /// scan is parallelized in chunks of [`INFER_CHUNK_SIZE`] once the store
+    /// holds at least [`PARALLEL_INFER_THRESHOLD`] crystals.
+    pub fn infer(&self, query: &Crystal4K) -> Option<Crystal4K> {
+        if let Some(index) = &self.index {
+            return index.nearest(query).map(|(_, crystal)| crystal);
+        }
+
+        #[cfg(feature = "rayon")]
+        {
+            if self.crystals.len() >= PARALLEL_INFER_THRESHOLD {
+                return self
+                    .crystals
+                    .par_chunks(INFER_CHUNK_SIZE)
+                    .filter_map(|chunk| Self::best_in_chunk(chunk, query))
+                    .min_by_key(|(distance, _)| *distance)
+                    .map(|(_, crystal)| crystal);
+            }
+        }
+        Self::best_in_chunk(&self.crystals, query).map(|(_, crystal)| crystal)
+    }
+
+    /// Reduces one chunk of crystals to its best (distance, crystal) match.
+    fn best_in_chunk(chunk: &[Crystal4K], query: &Crystal4K) -> Option<(u32, Crystal4K)> {
+        chunk
+            .iter()
+            .map(|crystal| (crystal.hamming_distance(query), crystal.clone()))
+            .min_by_key(|(distance, _)| *distance)
+    }
+}
+
+impl Default for CrystalMemory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crystal::fuzzy::DEFAULT_WINDOW_BITS;
+
+    /// Deterministic pseudo-random crystal, so tests are reproducible
+    /// without pulling in a `rand` dependency.
+    fn crystal(seed: u8) -> Crystal4K {
+        let mut state = seed;
+        let boundary = (0..64)
+            .map(|_| {
+                state = state.wrapping_mul(131).wrapping_add(7);
+                state
+            })
+            .collect();
+        Crystal4K { boundary }
+    }
+
+    fn linear_nearest_distance(crystals: &[Crystal4K], query: &Crystal4K) -> u32 {
+        crystals
+            .iter()
+            .map(|c| c.hamming_distance(query))
+            .min()
+            .unwrap()
+    }
+
+    #[test]
+    fn infer_matches_linear_scan_across_index_build_and_later_adds() {
+        let mut memory = CrystalMemory::new();
+        for seed in 0..20u8 {
+            memory.add(crystal(seed));
+        }
+        memory.build_index();
+        // Crystals added after the index was built must stay searchable via
+        // the index's incremental insert.
+        for seed in 20..40u8 {
+            memory.add(crystal(seed));
+        }
+
+        let all: Vec<Crystal4K> = (0..40u8).map(crystal).collect();
+        let query = crystal(99);
+        let expected = linear_nearest_distance(&all, &query);
+
+        let actual = memory.infer(&query).expect("memory is non-empty");
+        assert_eq!(actual.hamming_distance(&query), expected);
+    }
+
+    #[test]
+    fn search_fuzzy_empty_before_build_then_finds_exact_match_after() {
+        let mut memory = CrystalMemory::new();
+        for seed in 0..10u8 {
+            memory.add(crystal(seed));
+        }
+        assert!(memory.search_fuzzy(&crystal(0), 5).is_empty());
+
+        memory.build_fuzzy_index(DEFAULT_WINDOW_BITS);
+        // A crystal added after the fuzzy index was built must stay
+        // searchable via the index's incremental insert.
+        let late = crystal(123);
+        memory.add(late.clone());
+
+        let results = memory.search_fuzzy(&late, 1);
+        assert_eq!(results.first(), Some(&late));
+    }
+
+    #[test]
+    #[should_panic(expected = "one boundary width per store")]
+    fn add_rejects_mismatched_boundary_lengths() {
+        let mut memory = CrystalMemory::new();
+        memory.add(Crystal4K {
+            boundary: vec![0u8; 64],
+        });
+        memory.add(Crystal4K {
+            boundary: vec![0u8; 32],
+        });
+    }
+}
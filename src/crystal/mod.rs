@@ -50,10 +50,17 @@
 //! let result = memory.infer(&crystal);
 //! ```
 
-mod field;
 mod crystal4k;
+mod field;
+mod fuzzy;
+mod imatrix;
+mod index;
+mod lattice;
 mod memory;
 
-pub use field::{QuorumField, FIELD_SIZE, FIELD_CELLS};
 pub use crystal4k::Crystal4K;
+pub use field::{QuorumField, FIELD_CELLS, FIELD_SIZE};
+pub use fuzzy::DEFAULT_WINDOW_BITS;
+pub use imatrix::ImportanceMatrix;
+pub use lattice::Lattice;
 pub use memory::{CrystalMemory, MemoryStats, DEFAULT_CAPACITY, DEFAULT_SETTLE_STEPS};
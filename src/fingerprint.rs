@@ -0,0 +1,92 @@
+//! Content fingerprinting: maps arbitrary bytes onto the bits of a
+//! [`QuorumField`](crate::crystal::QuorumField), via an extendable-output
+//! function so the mapping scales to the field size without rehashing.
+
+use crate::crystal::FIELD_CELLS;
+use sha3::digest::{ExtendableOutput, Update, XofReader};
+use sha3::Shake256;
+
+/// Default domain-separation tag for [`Fingerprint::from_content`].
+///
+/// Callers who need distinct content namespaces (e.g. separate memory
+/// banks that must never collide) should call
+/// [`Fingerprint::from_content_with_domain`] with their own tag instead.
+pub const DEFAULT_DOMAIN: &[u8] = b"crystal-quorum-field-v1";
+
+/// A deterministic bit pattern over `FIELD_CELLS` bits, used to seed a
+/// [`QuorumField`](crate::crystal::QuorumField) via `inject`.
+#[derive(Clone, Debug)]
+pub struct Fingerprint {
+    pub(crate) words: Vec<u64>,
+}
+
+impl Fingerprint {
+    /// Derives a fingerprint from arbitrary content under the default
+    /// domain tag.
+    ///
+    /// The same content always produces the same fingerprint; distinct
+    /// content spreads uniformly across the field's bits.
+    pub fn from_content(content: &str) -> Self {
+        Self::from_content_with_domain(content.as_bytes(), DEFAULT_DOMAIN)
+    }
+
+    /// Derives a fingerprint from arbitrary content under an explicit
+    /// domain-separation tag.
+    ///
+    /// Runs `len(domain) || domain || content` through SHAKE256 (the
+    /// length is a little-endian `u64`, so no tag/content split of one
+    /// input can reproduce another's) and squeezes exactly
+    /// enough bytes to fill `FIELD_CELLS` bits, so the output length scales
+    /// with the field size without needing to rehash. Two calls with the
+    /// same content but different tags are expected to produce unrelated
+    /// fingerprints — useful for partitioning content namespaces (e.g. a
+    /// distinct tag per memory bank) so their seedings don't overlap.
+    ///
+    /// Deterministic and portable: SHAKE256 output doesn't depend on
+    /// platform endianness or word size.
+    pub fn from_content_with_domain(content: &[u8], domain: &[u8]) -> Self {
+        let word_count = FIELD_CELLS.div_ceil(64);
+        let mut xof = Shake256::default();
+        xof.update(&(domain.len() as u64).to_le_bytes());
+        xof.update(domain);
+        xof.update(content);
+        let mut reader = xof.finalize_xof();
+
+        let mut bytes = vec![0u8; word_count * 8];
+        reader.read(&mut bytes);
+
+        let words = bytes
+            .chunks_exact(8)
+            .map(|chunk| u64::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        Fingerprint { words }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_content_is_deterministic() {
+        let a = Fingerprint::from_content("quorum-field-seed");
+        let b = Fingerprint::from_content("quorum-field-seed");
+        assert_eq!(a.words, b.words);
+        assert_eq!(a.words.len(), FIELD_CELLS.div_ceil(64));
+    }
+
+    #[test]
+    fn different_domains_diverge() {
+        let content = b"same content, different namespace";
+        let a = Fingerprint::from_content_with_domain(content, b"domain-a");
+        let b = Fingerprint::from_content_with_domain(content, b"domain-b");
+        assert_ne!(a.words, b.words);
+    }
+
+    #[test]
+    fn different_content_diverges() {
+        let a = Fingerprint::from_content("alpha");
+        let b = Fingerprint::from_content("beta");
+        assert_ne!(a.words, b.words);
+    }
+}
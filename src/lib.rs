@@ -0,0 +1,6 @@
+//! ladybug: holographic crystal memory over quorum-voting 3D lattices.
+
+pub mod crystal;
+mod fingerprint;
+
+pub use fingerprint::Fingerprint;